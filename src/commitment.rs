@@ -0,0 +1,145 @@
+//! Pedersen commitments that allow everyone to check that the published sums of a participant are
+//! consistent with the shares that the participant has sent (and received).
+//!
+//! A commitment `g^x * h^r mod p` hides the value `x` (because of the random blinding factor `r`),
+//! but is binding and homomorphic: the product of commitments is a commitment to the sum of the
+//! values. Every participant publishes commitments to its secret and to the shares it sent, so
+//! that the commitment to its published sum can be computed by anyone and checked against the sum
+//! and the combined blinding factor.
+
+use rand::RngCore;
+use rsa::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// The 2048-bit MODP group from RFC 3526, `p` is a safe prime.
+const P: &str = "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF0598DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3BE39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF6955817183995497CEA956AE515D2261898FA051015728E5A8AACAA68FFFFFFFFFFFFFFFF";
+
+const GROUP_BYTES: usize = 256;
+
+/// A commitment to a single (fixed-point) value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitment(Vec<u8>);
+
+/// The random blinding factor of a commitment, needed to open it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blinding(Vec<u8>);
+
+/// The subgroup of quadratic residues modulo `p`, of prime order `q = (p - 1) / 2`.
+pub struct Group {
+    p: BigUint,
+    q: BigUint,
+    g: BigUint,
+    h: BigUint,
+}
+
+impl Group {
+    pub fn new() -> Self {
+        let p = BigUint::parse_bytes(P.as_bytes(), 16).expect("valid hex");
+        let q = (&p - 1u32) / 2u32;
+        // any square is a generator of the subgroup of prime order q:
+        let g = BigUint::from(4u32);
+        // nobody knows the discrete logarithm of h to the base g, since it is derived from a hash:
+        let mut bytes = [0; GROUP_BYTES + 16];
+        blake3::Hasher::new()
+            .update(b"sine-benchmark pedersen h")
+            .finalize_xof()
+            .fill(&mut bytes);
+        let h = BigUint::from_bytes_be(&bytes).modpow(&BigUint::from(2u32), &p);
+        Group { p, q, g, h }
+    }
+
+    pub fn random_blinding<R: RngCore>(&self, rng: &mut R) -> Blinding {
+        let mut bytes = [0; GROUP_BYTES + 16];
+        rng.fill_bytes(&mut bytes);
+        Blinding((BigUint::from_bytes_be(&bytes) % &self.q).to_bytes_be())
+    }
+
+    pub fn commit(&self, value: i128, blinding: &Blinding) -> Commitment {
+        self.commit_exponent(&self.exponent(value, 0), blinding)
+    }
+
+    /// Checks whether the commitment opens to the value.
+    pub fn opens(&self, commitment: &Commitment, value: i128, blinding: &Blinding) -> bool {
+        self.commit(value, blinding) == *commitment
+    }
+
+    /// Combines the blinding factors in the same way as the values are combined into the sum.
+    pub fn combine<'a>(
+        &self,
+        own: &Blinding,
+        sent: impl IntoIterator<Item = &'a Blinding>,
+        received: impl IntoIterator<Item = &'a Blinding>,
+    ) -> Blinding {
+        let mut combined = BigUint::from_bytes_be(&own.0) % &self.q;
+        for blinding in sent {
+            let blinding = BigUint::from_bytes_be(&blinding.0) % &self.q;
+            combined = (combined + &self.q - blinding) % &self.q;
+        }
+        for blinding in received {
+            combined = (combined + BigUint::from_bytes_be(&blinding.0)) % &self.q;
+        }
+        Blinding(combined.to_bytes_be())
+    }
+
+    /// Checks that the sum (with the given carry, see [`carry`]) is the secret minus the sent
+    /// shares plus the received shares, using only the commitments to them.
+    pub fn verify_sum<'a>(
+        &self,
+        sum: i128,
+        carry: i64,
+        blinding: &Blinding,
+        own: &Commitment,
+        sent: impl IntoIterator<Item = &'a Commitment>,
+        received: impl IntoIterator<Item = &'a Commitment>,
+    ) -> bool {
+        let mut expected = BigUint::from_bytes_be(&own.0) % &self.p;
+        for commitment in sent {
+            let inverse = BigUint::from_bytes_be(&commitment.0).modpow(&(&self.p - 2u32), &self.p);
+            expected = (expected * inverse) % &self.p;
+        }
+        for commitment in received {
+            expected = (expected * BigUint::from_bytes_be(&commitment.0)) % &self.p;
+        }
+        self.commit_exponent(&self.exponent(sum, carry), blinding).0 == expected.to_bytes_be()
+    }
+
+    /// The value `x + carry * 2^128` (with `x` interpreted as an unsigned integer) modulo `q`.
+    fn exponent(&self, value: i128, carry: i64) -> BigUint {
+        let value = BigUint::from_bytes_be(&(value as u128).to_be_bytes());
+        let carry_value = (BigUint::from(carry.unsigned_abs()) << 128) % &self.q;
+        if carry >= 0 {
+            (value + carry_value) % &self.q
+        } else {
+            (value + &self.q - carry_value) % &self.q
+        }
+    }
+
+    fn commit_exponent(&self, exponent: &BigUint, blinding: &Blinding) -> Commitment {
+        let blinding = BigUint::from_bytes_be(&blinding.0);
+        let commitment =
+            (self.g.modpow(exponent, &self.p) * self.h.modpow(&blinding, &self.p)) % &self.p;
+        Commitment(commitment.to_bytes_be())
+    }
+}
+
+/// The number of times that the (wrapping) sum overflowed the 128-bit integers, so that the exact
+/// sum of the secret minus the sent shares plus the received shares is `sum + carry * 2^128`.
+pub fn carry(
+    secret: i128,
+    sent: impl IntoIterator<Item = i128>,
+    received: impl IntoIterator<Item = i128>,
+) -> i64 {
+    let mut sum = secret as u128;
+    let mut carry = 0;
+    for share in sent {
+        let (difference, borrow) = sum.overflowing_sub(share as u128);
+        sum = difference;
+        carry -= borrow as i64;
+    }
+    for share in received {
+        let (total, overflow) = sum.overflowing_add(share as u128);
+        sum = total;
+        carry += overflow as i64;
+    }
+    carry
+}
@@ -0,0 +1,877 @@
+//! User-facing messages and their translations.
+//!
+//! Every message has an English text, translations are optional and fall back to English, so new
+//! messages can be added without having to translate them right away.
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// English
+    En,
+    /// German
+    De,
+}
+
+impl Lang {
+    /// Picks the language based on the `LANG` environment variable, defaulting to English.
+    pub fn from_env() -> Self {
+        match std::env::var("LANG") {
+            Ok(lang) if lang.starts_with("de") => Lang::De,
+            _ => Lang::En,
+        }
+    }
+}
+
+/// A message shown to the user.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event")]
+pub enum Text<'a> {
+    LeaderWithAddress,
+    StdinInputNeeds {
+        flag: &'static str,
+    },
+    ParticipantWithoutAddress,
+    NoUpnpNeedsExternalAddr,
+    NoSuchFile {
+        path: &'a str,
+    },
+    CouldNotReadFile {
+        path: &'a str,
+        error: &'a str,
+    },
+    InputHashMismatch {
+        path: &'a str,
+        hash: &'a str,
+    },
+    InvalidJson {
+        path: &'a str,
+    },
+    NotANumber {
+        path: &'a str,
+        key: &'a str,
+    },
+    DuplicateKey {
+        path: &'a str,
+        key: &'a str,
+    },
+    ReservedCharacterInKey {
+        path: &'a str,
+        key: &'a str,
+    },
+    MissingDeltaField {
+        key: &'a str,
+        field: &'a str,
+    },
+    TooPrecise {
+        key: &'a str,
+        value: f64,
+        decimals: u32,
+    },
+    InputHeader {
+        scale: i128,
+    },
+    ReconstructionHeader,
+    MaskedHeader,
+    ReconstructionLine {
+        key: &'a str,
+        value: f64,
+        scale: i128,
+        secret: i128,
+        sent_sum: i128,
+        masked: i128,
+    },
+    JoiningSession {
+        addr: &'a str,
+    },
+    WaitingForSession {
+        addr: &'a str,
+    },
+    GeneratingKeys,
+    YourPublicKey {
+        key: &'a str,
+    },
+    SessionStarted,
+    SessionCode {
+        code: &'a str,
+    },
+    AlsoReachableAt {
+        family: &'a str,
+    },
+    SearchingLocalSession,
+    PressEnterToStart,
+    PauseHint,
+    ParticipantsHeader,
+    StaleParticipant {
+        key: &'a str,
+        name: &'a str,
+    },
+    ParticipantDisconnected {
+        name: &'a str,
+    },
+    LobbyPausedByYou,
+    LobbyResumedByYou,
+    CannotStartWhilePaused,
+    LobbyPaused,
+    LobbyResumed,
+    LobbyClosesIn {
+        secs: u64,
+    },
+    LobbyTimeoutReached,
+    LobbyTimedOut,
+    SharesTimedOut {
+        names: &'a str,
+    },
+    NotEnoughParticipants {
+        needed: usize,
+    },
+    DuplicateName {
+        name: &'a str,
+        renamed: &'a str,
+    },
+    NameTaken {
+        name: &'a str,
+    },
+    MinParticipantsInLobby {
+        needed: usize,
+    },
+    TooFewParticipantsStarted {
+        needed: usize,
+    },
+    StartingBenchmark,
+    SymmetricSession,
+    SumSession,
+    VarianceSession,
+    RatioOfSumsSession {
+        ratios: &'a str,
+    },
+    MeanOfRatiosSession {
+        ratios: &'a str,
+    },
+    UnknownRatioKey {
+        key: &'a str,
+    },
+    RatioResults,
+    KeyBitsMismatch {
+        name: &'a str,
+        bits: usize,
+        expected: usize,
+    },
+    ScaleMismatch {
+        name: &'a str,
+        scale: i128,
+        expected: i128,
+    },
+    MissingKeys {
+        name: &'a str,
+        keys: &'a str,
+    },
+    UniqueKeys {
+        name: &'a str,
+        keys: &'a str,
+    },
+    KeysMustMatch,
+    VectorLengthsDiffer {
+        key: &'a str,
+    },
+    VectorLength {
+        name: &'a str,
+        key: &'a str,
+        length: usize,
+    },
+    OwnVectorLengthDiffers {
+        key: &'a str,
+        length: usize,
+        expected: usize,
+    },
+    InvalidSchema {
+        path: &'a str,
+        error: &'a str,
+    },
+    SchemaKeyMissing {
+        key: &'a str,
+    },
+    SchemaKeyUnexpected {
+        key: &'a str,
+    },
+    SchemaValueTooSmall {
+        key: &'a str,
+        value: f64,
+        min: f64,
+    },
+    SchemaValueTooLarge {
+        key: &'a str,
+        value: f64,
+        max: f64,
+    },
+    GeoMeanSession,
+    GeoMeanResults,
+    ValueOutOfRange {
+        key: &'a str,
+        value: f64,
+        max: f64,
+    },
+    NotFinite {
+        key: &'a str,
+        value: f64,
+    },
+    NotPositiveForGeoMean {
+        key: &'a str,
+        value: f64,
+    },
+    NoNoiseForGeoMean,
+    HistogramSession {
+        edges: &'a str,
+    },
+    HistogramResults,
+    WeightedMeanSession {
+        field: &'a str,
+    },
+    InvalidParticipantWeight {
+        field: &'a str,
+        weight: f64,
+    },
+    VerifiedSession,
+    TimeSpent,
+    TimeSpentIn {
+        step: &'a str,
+        seconds: f64,
+    },
+    ThresholdSession {
+        threshold: usize,
+    },
+    ConfirmationsRequired,
+    MissingKeysAsZero,
+    ParticipantConfirmed {
+        name: &'a str,
+    },
+    ParticipantDeclined {
+        name: &'a str,
+    },
+    InconsistentShare {
+        name: &'a str,
+    },
+    InconsistentSum {
+        name: &'a str,
+    },
+    WeightedSession {
+        weights: &'a str,
+    },
+    UnknownWeightedKey {
+        key: &'a str,
+    },
+    CompositeResult {
+        value: f64,
+        precision: usize,
+    },
+    StratifiedSession {
+        tag: &'a str,
+        min: usize,
+    },
+    NoisySession {
+        epsilon: f64,
+        sensitivity: f64,
+    },
+    ConfirmParticipants,
+    JoiningBenchmark,
+    DryRunComplete,
+    NotADryRun,
+    InvalidConfirmation,
+    NotEveryoneAgreed,
+    BenchmarkCancelled,
+    AlreadySendingShares,
+    InvalidKeyReceived {
+        key: &'a str,
+    },
+    ConnectionsRefused {
+        count: usize,
+    },
+    IncomingConnectionError,
+    CheckingAddress {
+        addr: &'a str,
+    },
+    AddressNotReachable {
+        addr: &'a str,
+    },
+    UndecodableMessages {
+        peer: &'a str,
+    },
+    IncompatibleVersion {
+        peer: &'a str,
+        version: u32,
+        own: u32,
+    },
+    TooManyMessages {
+        peer: &'a str,
+    },
+    ConnectionError,
+    ParticipantLeft {
+        name: &'a str,
+    },
+    SomeParticipantLeft,
+    Reconnecting {
+        secs: u64,
+    },
+    Reconnected,
+    AverageResults,
+    TotalResults,
+    PartialResults {
+        contributors: usize,
+        participants: usize,
+    },
+    ExcludingParticipant {
+        name: &'a str,
+    },
+    ExcludedFromResult,
+    SumWithheld,
+    ResultVerified,
+    ResultMismatch,
+    ResultNotVerified {
+        received: usize,
+        needed: usize,
+    },
+    TooFewContributors {
+        needed: usize,
+    },
+    UniformInputs,
+    ComparisonHeader {
+        path: &'a str,
+    },
+    InputUnchanged {
+        path: &'a str,
+    },
+    NotInComparison {
+        key: &'a str,
+    },
+    CouldNotListen {
+        port: u16,
+        error: &'a str,
+    },
+    CouldNotSaveResult {
+        path: &'a str,
+        error: &'a str,
+    },
+    TranscriptNotSigned,
+    TranscriptValid {
+        leader: &'a str,
+    },
+    TranscriptInvalid {
+        error: &'a str,
+    },
+    ProxyFailed {
+        error: &'a str,
+    },
+    RelayFailed {
+        error: &'a str,
+    },
+    RelayedSession,
+    PushedResults {
+        url: &'a str,
+    },
+    PushFailed {
+        url: &'a str,
+        error: &'a str,
+    },
+    KickHint,
+    ParticipantKicked {
+        name: &'a str,
+    },
+    UnknownParticipant {
+        name: &'a str,
+    },
+    AmbiguousParticipant {
+        prefix: &'a str,
+    },
+    RemovedFromSession,
+    TuiNotAvailable,
+    JoiningSessionNumber {
+        number: usize,
+        addr: &'a str,
+    },
+    SessionFailed {
+        error: &'a str,
+    },
+    SessionPerAddress,
+    SqlNotAvailable,
+    SqlFailed {
+        path: &'a str,
+        error: &'a str,
+    },
+    InvalidCsv {
+        path: &'a str,
+        error: &'a str,
+    },
+    MissingColumn {
+        path: &'a str,
+        column: &'a str,
+    },
+    NotANumberInRow {
+        path: &'a str,
+        key: &'a str,
+        value: &'a str,
+    },
+    DuplicateRow {
+        path: &'a str,
+        key: &'a str,
+    },
+    ParticipantsTitle,
+    MessagesTitle,
+    ResultsTitle,
+    LeaderKeys,
+    ParticipantKeys,
+    PressAnyKeyToQuit,
+    StatusWaiting {
+        participants: usize,
+    },
+    StatusConfirming,
+    SharesProgress {
+        received: usize,
+        expected: usize,
+    },
+    SumsProgress {
+        received: usize,
+        expected: usize,
+    },
+    StatusSendingShares {
+        received: usize,
+        expected: usize,
+    },
+    StatusDone,
+}
+
+impl Text<'_> {
+    pub fn translate(&self, lang: Lang) -> String {
+        match lang {
+            Lang::En => self.en(),
+            Lang::De => self.de().unwrap_or_else(|| self.en()),
+        }
+    }
+
+    /// The message as a JSON line with its name as the event and its fields, see `--json-logs`.
+    pub fn to_json(&self, level: &str, lang: Lang, session: Option<usize>) -> String {
+        #[derive(Serialize)]
+        struct JsonLine<'a, 'b> {
+            #[serde(flatten)]
+            text: &'a Text<'b>,
+            level: &'a str,
+            message: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            session: Option<usize>,
+        }
+        let line = JsonLine {
+            text: self,
+            level,
+            message: self.translate(lang),
+            session,
+        };
+        serde_json::to_string(&line).unwrap_or_else(|e| {
+            serde_json::json!({"event": "Error", "message": e.to_string()}).to_string()
+        })
+    }
+
+    fn en(&self) -> String {
+        match self {
+            Text::LeaderWithAddress => {
+                "A leader starts a new session and cannot use --address.".into()
+            }
+            Text::StdinInputNeeds { flag } => format!("With --input=-, the input is read from stdin, which can then no longer be used to answer any questions, please also use {flag}."),
+            Text::ParticipantWithoutAddress => {
+                "Participants need to specify the --address of the session to join (or --local).".into()
+            }
+            Text::NoUpnpNeedsExternalAddr => "Without UPnP, a new session needs the --external-addr that participants can reach it at (or --local or --relay).".into(),
+            Text::NoSuchFile { path } => format!(
+                "No such file: {path}\nThe input must be a JSON file with key-value pairs."
+            ),
+            Text::CouldNotReadFile { path, error } => {
+                format!("Could not read file {path}: {error}")
+            }
+            Text::InputHashMismatch { path, hash } => format!("Input file hash mismatch: the blake3 hash of {path} is {hash}, the file might be corrupted or the wrong file."),
+            Text::InvalidJson { path } => format!("The file {path} is not a valid JSON file with a map of string keys and integer number values."),
+            Text::NotANumber { path, key } => format!("The file {path} is not a valid JSON file with a map of string keys and integer number values, '{key}' is not a number."),
+            Text::DuplicateKey { path, key } => format!("The file {path} contains the key '{key}' more than once (nested keys are joined with '.')."),
+            Text::ReservedCharacterInKey { path, key } => format!("The input {path} contains the key '{}', but keys must not contain null characters.", key.escape_default()),
+            Text::MissingDeltaField { key, field } => format!("The key '{key}' has no '{field}' value, with --deltas every key needs both a 'current' and a 'baseline' value."),
+            Text::TooPrecise { key, value, decimals } => format!("The value {value} of key '{key}' has more than {decimals} decimal places, please round it explicitly."),
+            Text::InputHeader { scale } => format!("-- Input (x {scale}, rounded) --"),
+            Text::MaskedHeader => "\n-- Masked values --\nThese masked values are what leaves your machine (besides the encrypted shares). They are masked by the random shares of all participants and reveal nothing about your input on their own.".into(),
+            Text::ReconstructionHeader => {
+                "\n-- Reconstruction of your contribution (not sent to anyone) --".into()
+            }
+            Text::ReconstructionLine {
+                key,
+                value,
+                scale,
+                secret,
+                sent_sum,
+                masked,
+            } => format!(
+                "{key}: input {value} x {scale} = {secret}, sum of sent shares = {sent_sum}, masked = {masked}, masked + sum of sent shares = {}",
+                masked.wrapping_add(*sent_sum)
+            ),
+            Text::JoiningSession { addr } => format!("Joining session at {addr}..."),
+            Text::WaitingForSession { addr } => {
+                format!("Waiting for session to start at {addr}...")
+            }
+            Text::GeneratingKeys => "Generating public/private key pair...".into(),
+            Text::YourPublicKey { key } => format!("Your public key is: {key}"),
+            Text::SearchingLocalSession => "Searching for a session on the local network...".into(),
+            Text::SessionStarted => {
+                "A new session has been started, others can join using the following command:"
+                    .into()
+            }
+            Text::SessionCode { code } => format!("Instead of the --address, they can also use the session code: --join={code}"),
+            Text::AlsoReachableAt { family } => format!("Participants can also join via {family} using the following command:"),
+            Text::PressEnterToStart => {
+                "\nPress ENTER to start the benchmark once all participants have joined.".into()
+            }
+            Text::PauseHint => {
+                "Type /pause to prevent the benchmark from being started until you /resume."
+                    .into()
+            }
+            Text::ParticipantsHeader => "\n-- Participants --".into(),
+            Text::StaleParticipant { key, name } => {
+                format!("{key} - {name} (possibly disconnected)")
+            }
+            Text::ParticipantDisconnected { name } => {
+                format!("\nParticipant {name} disconnected")
+            }
+            Text::LobbyPausedByYou => {
+                "The lobby is paused, type /resume to allow starting the benchmark again.".into()
+            }
+            Text::LobbyResumedByYou => {
+                "The lobby has been resumed, press ENTER to start the benchmark.".into()
+            }
+            Text::CannotStartWhilePaused => {
+                "Cannot start while the lobby is paused, type /resume first.".into()
+            }
+            Text::LobbyPaused => "\nThe lobby has been paused, the benchmark will not start until it is resumed.".into(),
+            Text::LobbyResumed => {
+                "\nThe lobby has been resumed, the benchmark can now be started.".into()
+            }
+            Text::LobbyClosesIn { secs } => format!(
+                "The lobby closes in {}:{:02} minutes, the benchmark then starts if enough participants have joined.",
+                secs / 60,
+                secs % 60
+            ),
+            Text::LobbyTimeoutReached => "\nThe lobby timeout has been reached.".into(),
+            Text::LobbyTimedOut => {
+                "The lobby has timed out before the benchmark could be started, aborting the session.".into()
+            }
+            Text::SharesTimedOut { names } => format!("No shares have been received from {names} before the --share-timeout, aborting the benchmark."),
+            Text::NotEnoughParticipants { needed } => format!(
+                "Cannot start yet, at least {needed} participants are needed to ensure privacy."
+            ),
+            Text::DuplicateName { name, renamed } => format!(
+                "Another participant is already called {name}, the new participant is shown as {renamed}."
+            ),
+            Text::NameTaken { name } => format!(
+                "Another participant already uses your name, you are shown as {name}. Consider choosing a different --name."
+            ),
+            Text::MinParticipantsInLobby { needed } => {
+                format!("At least {needed} participants are needed to start the benchmark.")
+            }
+            Text::TooFewParticipantsStarted { needed } => format!(
+                "Someone tried to start a benchmark with fewer than {needed} participants!"
+            ),
+            Text::StartingBenchmark => "Starting benchmark with the current participants...".into(),
+            Text::SymmetricSession => {
+                "Every participant will compute the result independently (no central aggregator)."
+                    .into()
+            }
+            Text::SumSession => {
+                "This session computes the total of all values instead of their average.".into()
+            }
+            Text::WeightedMeanSession { field } => format!("The averages will be weighted by the '{field}' field in the input of every participant (1 if missing)."),
+            Text::InvalidParticipantWeight { field, weight } => format!("The '{field}' field in your input must be a positive number, but it is {weight}."),
+            Text::RatioOfSumsSession { ratios } => format!("The ratios {ratios} will also be reported, as the total of the numerators divided by the total of the denominators."),
+            Text::MeanOfRatiosSession { ratios } => format!("The ratios {ratios} will also be reported, as the average of the ratios of every participant."),
+            Text::UnknownRatioKey { key } => format!("Your input does not contain the key '{key}' of the ratio."),
+            Text::RatioResults => "\nRatios:".into(),
+            Text::KeyBitsMismatch { name, bits, expected } => format!("{name} uses a key with {bits} bits instead of {expected} bits, all participants must use the same --key-bits."),
+            Text::ScaleMismatch { name, scale, expected } => format!("{name} uses a scale of {scale} instead of {expected}, all participants must use the same --scale."),
+            Text::MissingKeys { name, keys } => format!("{name} does not have the keys: {keys}"),
+            Text::UniqueKeys { name, keys } => format!("Only {name} has the keys: {keys}"),
+            Text::KeysMustMatch => "The benchmark can only be started once all participants have the same keys (--missing-key-policy=error).".into(),
+            Text::VectorLengthsDiffer { key } => format!("The benchmark can only be started once all participants have the same number of values for '{key}'."),
+            Text::VectorLength { name, key, length } => format!("{name} has {length} values for '{key}'."),
+            Text::OwnVectorLengthDiffers { key, length, expected } => format!("Your input has {length} values for '{key}', but the benchmark was started with {expected} values for it, so you cannot join the benchmark."),
+            Text::InvalidSchema { path, error } => format!("The file {path} is not a valid schema, which needs to be a JSON object with a list of \"keys\" and optionally their \"bounds\": {error}"),
+            Text::SchemaKeyMissing { key } => format!("Your input does not conform to the schema of the session, the key '{key}' is missing. You cannot confirm the participants until you add it to your input and join again."),
+            Text::SchemaKeyUnexpected { key } => format!("Your input does not conform to the schema of the session, the key '{key}' is not part of the schema. You cannot confirm the participants until you remove it from your input and join again."),
+            Text::SchemaValueTooSmall { key, value, min } => format!("The value {value} of '{key}' is smaller than {min}, the minimum that the schema of the session allows. Please check that the value is correct and uses the expected unit."),
+            Text::SchemaValueTooLarge { key, value, max } => format!("The value {value} of '{key}' is larger than {max}, the maximum that the schema of the session allows. Please check that the value is correct and uses the expected unit (e.g. dollars instead of cents)."),
+            Text::GeoMeanSession => "This session computes the geometric mean of all values instead of their average.".into(),
+            Text::GeoMeanResults => "\nResults (geometric mean):".into(),
+            Text::ValueOutOfRange { key, value, max } => format!("The value {value} of key '{key}' is too large, only values between -{max} and {max} can be summed up without overflowing."),
+            Text::NotFinite { key, value } => format!("The value of key '{key}' is {value}, but only finite numbers can be benchmarked."),
+            Text::NotPositiveForGeoMean { key, value } => format!("The geometric mean can only be computed for positive values, but the value of '{key}' is {value}."),
+            Text::NoNoiseForGeoMean => "The geometric mean cannot be combined with --dp-epsilon, since the noise could make values negative.".into(),
+            Text::HistogramSession { edges } => format!("The number of participants per bucket (with the edges {edges}) will also be reported, which reveals the distribution of the values."),
+            Text::HistogramResults => "\nDistribution:".into(),
+            Text::VarianceSession => "The standard deviation of every key will also be reported.".into(),
+            Text::StratifiedSession { tag, min } => format!("The results will also be reported per {tag}, for all groups of at least {min} participants."),
+            Text::WeightedSession { weights } => format!("A weighted composite of the results will also be reported (weights: {weights})."),
+            Text::UnknownWeightedKey { key } => format!("Your input does not contain the weighted key '{key}'."),
+            Text::CompositeResult { value, precision } => format!("\nWeighted composite: {value:.precision$}"),
+            Text::ConfirmationsRequired => "The benchmark will only start once every participant has confirmed the participants.".into(),
+            Text::MissingKeysAsZero => "Missing keys are counted as a value of zero (--missing-key-policy=zero).".into(),
+            Text::ParticipantConfirmed { name } => format!("{name} has confirmed the participants."),
+            Text::ParticipantDeclined { name } => format!("{name} has declined to join the benchmark, aborting the benchmark."),
+            Text::TimeSpent => "\nTime spent per step:".into(),
+            Text::TimeSpentIn { step, seconds } => format!("  {step}: {seconds:.3}s"),
+            Text::VerifiedSession => "Every participant will publish commitments to their shares, so that everyone can check that the sums are consistent with the shares.".into(),
+            Text::ThresholdSession { threshold } => format!("The result can only be reconstructed from the sums of at least {threshold} participants."),
+            Text::InconsistentShare { name } => format!("The shares sent by {name} do not match the published commitments, aborting the benchmark."),
+            Text::InconsistentSum { name } => format!("The sum of {name} does not match the committed shares ({name} or one of the participants that sent shares to {name} has cheated), aborting the benchmark."),
+            Text::NoisySession { epsilon, sensitivity } => format!("Every participant adds random noise to their values (epsilon = {epsilon}, sensitivity = {sensitivity}), the results will only be approximate."),
+            Text::ConfirmParticipants => "Please double-check the participants. Do you want to join the benchmark? [Y/n]".into(),
+            Text::JoiningBenchmark => {
+                "Ok, joining benchmarking with the current participants...".into()
+            }
+            Text::DryRunComplete => "Dry run complete: the input is valid and all participants are connected. No shares have been sent.".into(),
+            Text::NotADryRun => "The session has been started without --dry-run, leaving before any shares are sent. This aborts the benchmark for the others.".into(),
+            Text::InvalidConfirmation => {
+                "Invalid input, please confirm or cancel using 'y' or 'n'".into()
+            }
+            Text::NotEveryoneAgreed => {
+                "Not everyone agreed to participate, exiting without running the benchmark.".into()
+            }
+            Text::BenchmarkCancelled => {
+                "The benchmark was cancelled by one of the participants, exiting.".into()
+            }
+            Text::AlreadySendingShares => {
+                "Already waiting for shares, but some participant still tried to join!".into()
+            }
+            Text::InvalidKeyReceived { key } => {
+                format!("Received invalid key {key} from one of the participants!")
+            }
+            Text::ConnectionsRefused { count } => format!("Refused {count} incoming connection(s), the connection limit has been reached"),
+            Text::IncomingConnectionError => "Error while establishing incoming connection".into(),
+            Text::CheckingAddress { addr } => format!("Checking whether {addr} is reachable..."),
+            Text::AddressNotReachable { addr } => format!("Warning: {addr} (reported by your router) could not be reached from this computer. Your network might be behind a second NAT (for example carrier-grade NAT of your ISP), in which case participants will not be able to join. Try to forward a port manually or start the session on a machine with a public IP address."),
+            Text::TooManyMessages { peer } => format!("Peer {peer} is sending too many messages, dropping its messages until it slows down (see --max-message-rate)."),
+            Text::IncompatibleVersion { peer, version, own } => format!("Peer {peer} uses the incompatible protocol version {version} (instead of version {own}), ignoring all messages from this peer. Please make sure that everyone uses the same version of sine-benchmark."),
+            Text::UndecodableMessages { peer } => format!("Peer {peer} is sending undecodable messages (version mismatch?), ignoring all further messages from this peer. Please make sure that everyone uses the same version of sine-benchmark."),
+            Text::ConnectionError => "Connection error, please try again.".into(),
+            Text::ParticipantLeft { name } => {
+                format!("Participant {name} left, aborting the benchmark.")
+            }
+            Text::SomeParticipantLeft => "A participant left, aborting the benchmark.".into(),
+            Text::Reconnecting { secs } => format!("The connection to a participant was lost, waiting up to {secs} seconds for it to be restored..."),
+            Text::Reconnected => "The connection has been restored, continuing the benchmark.".into(),
+            Text::AverageResults => "\nAverage results:".into(),
+            Text::TotalResults => "\nTotal results:".into(),
+            Text::PartialResults {
+                contributors,
+                participants,
+            } => format!("(partial: {contributors} of {participants} participants)"),
+            Text::ExcludingParticipant { name } => {
+                format!("The deadline has passed, excluding {name} from the result.")
+            }
+            Text::ExcludedFromResult => "You did not send your sum before the deadline and have been excluded from the result.".into(),
+            Text::SumWithheld => "Your sum was not ready before the deadline of the first participant, so it is not sent: once you are excluded from the result, it would reveal your input.".into(),
+            Text::ResultVerified => {
+                "The result matches the sums of all participants.".into()
+            }
+            Text::ResultMismatch => "\nWARNING: The result sent by the first participant does NOT match the sums of the participants! It is wrong or has been manipulated, aborting.".into(),
+            Text::ResultNotVerified { received, needed } => format!(
+                "The result could not be checked, only {received} of {needed} sums have been received."
+            ),
+            Text::TooFewContributors { needed } => format!("The deadline has passed, but fewer than {needed} participants have sent their sums, aborting the benchmark."),
+            Text::UniformInputs => "Warning: the results are exactly what they would be if all participants had the same input as you, so the average most likely reveals the input of every participant.".into(),
+            Text::ProxyFailed { error } => format!("Could not connect to the session through the proxy: {error}"),
+            Text::RelayFailed { error } => format!("Could not be reached through the relay: {error}"),
+            Text::RelayedSession => "The session is reachable through the relay, all connections are forwarded by the relay.".into(),
+            Text::ComparisonHeader { path } => format!("\nChanges compared to {path}:"),
+            Text::InputUnchanged { path } => format!("Note: your input is unchanged since the run that saved {path}, please check that the source of your data is still updated if you expected new values."),
+            Text::NotInComparison { key } => format!("{key}: (not in the previous result)"),
+            Text::CouldNotListen { port: 0, error } => format!("Could not listen for connections: {error}"),
+            Text::CouldNotListen { port, error } => format!("Could not listen for connections on port {port}, it might already be in use: {error}"),
+            Text::CouldNotSaveResult { path, error } => {
+                format!("Could not save the results to {path}: {error}")
+            }
+            Text::TranscriptNotSigned => "No transcript was written, the result of a --symmetric session is not signed by the leader.".into(),
+            Text::TranscriptValid { leader } => format!("The transcript is valid and has been signed by the leader with the key {leader}."),
+            Text::TranscriptInvalid { error } => format!("The transcript is NOT valid: {error}"),
+            Text::PushedResults { url } => format!("Pushed the results to {url}"),
+            Text::PushFailed { url, error } => {
+                format!("Could not push the results to {url}: {error}")
+            }
+            Text::KickHint => "Type /kick <name or key> to remove a participant from the session.".into(),
+            Text::ParticipantKicked { name } => format!("Removed {name} from the session."),
+            Text::UnknownParticipant { name } => format!("There is no other participant called '{name}'."),
+            Text::AmbiguousParticipant { prefix } => format!("Several participants have a key starting with '{prefix}', please type more of the key."),
+            Text::RemovedFromSession => "You have been removed from the session.".into(),
+            Text::TuiNotAvailable => "--tui is not available, please build with --features tui.".into(),
+            Text::JoiningSessionNumber { number, addr } => format!("Joining the session at {addr} (type '{number} <answer>' to only answer this session)"),
+            Text::SessionFailed { error } => format!("The session failed: {error}"),
+            Text::SessionPerAddress => "Joining several sessions at once needs a different --session for every --address or --join (in the same order), as chosen by the first participant of each session.".into(),
+            Text::SqlNotAvailable => "--input-sql is not available, please build with --features sqlite.".into(),
+            Text::SqlFailed { path, error } => format!("The query on the database {path} failed: {error}"),
+            Text::InvalidCsv { path, error } => format!("The file {path} is not a valid CSV file: {error}"),
+            Text::MissingColumn { path, column } => format!("The file {path} has no column '{column}'."),
+            Text::NotANumberInRow { path, key, value } => format!("The value '{value}' of '{key}' in {path} is not a number."),
+            Text::DuplicateRow { path, key } => format!("The input {path} contains more than one value for '{key}'."),
+            Text::ParticipantsTitle => " Participants ".into(),
+            Text::MessagesTitle => " Messages ".into(),
+            Text::ResultsTitle => " Results ".into(),
+            Text::LeaderKeys => "ENTER start | p pause | r resume | ↑/↓ k kick | q quit".into(),
+            Text::ParticipantKeys => "ENTER/y join | n leave | q quit".into(),
+            Text::PressAnyKeyToQuit => "Press any key to quit.".into(),
+            Text::StatusWaiting { participants } => {
+                format!("Waiting for participants ({participants} joined)")
+            }
+            Text::StatusConfirming => "Confirm the participants to join the benchmark".into(),
+            Text::StatusSendingShares { received, expected } => {
+                format!("Exchanging shares ({received} of {expected} received)")
+            }
+            Text::SharesProgress { received, expected } => {
+                format!("Received shares from {received}/{expected} participants")
+            }
+            Text::SumsProgress { received, expected } => {
+                format!("Received sums from {received}/{expected} participants")
+            }
+            Text::StatusDone => "Benchmark completed".into(),
+        }
+    }
+
+    fn de(&self) -> Option<String> {
+        let text = match self {
+            Text::LeaderWithAddress => {
+                "Wer eine neue Sitzung startet, kann --address nicht verwenden.".into()
+            }
+            Text::StdinInputNeeds { flag } => format!("Mit --input=- wird die Eingabe von stdin gelesen, die dann nicht mehr für Antworten verwendet werden kann, bitte verwende zusätzlich {flag}."),
+            Text::ParticipantWithoutAddress => {
+                "Teilnehmende müssen die --address der Sitzung (oder --local) angeben.".into()
+            }
+            Text::NoUpnpNeedsExternalAddr => "Ohne UPnP braucht eine neue Sitzung die --external-addr, unter der die Teilnehmenden sie erreichen können (oder --local oder --relay).".into(),
+            Text::NoSuchFile { path } => format!(
+                "Datei nicht gefunden: {path}\nDie Eingabe muss eine JSON-Datei mit Schlüssel-Wert-Paaren sein."
+            ),
+            Text::CouldNotReadFile { path, error } => {
+                format!("Die Datei {path} konnte nicht gelesen werden: {error}")
+            }
+            Text::InputHashMismatch { path, hash } => format!("Die Prüfsumme der Datei {path} stimmt nicht überein (die tatsächliche blake3-Prüfsumme ist {hash}), die Datei ist möglicherweise beschädigt oder die falsche Datei."),
+            Text::InvalidJson { path } => format!("Die Datei {path} ist keine gültige JSON-Datei mit Text-Schlüsseln und Zahlenwerten."),
+            Text::NotANumber { path, key } => format!("Die Datei {path} ist keine gültige JSON-Datei mit Text-Schlüsseln und Zahlenwerten, '{key}' ist keine Zahl."),
+            Text::ValueOutOfRange { key, value, max } => format!("Der Wert {value} von '{key}' ist zu groß, nur Werte zwischen -{max} und {max} können ohne Überlauf summiert werden."),
+            Text::NotFinite { key, value } => format!("Der Wert von '{key}' ist {value}, es können aber nur endliche Zahlen verglichen werden."),
+            Text::TooPrecise { key, value, decimals } => format!("Der Wert {value} von '{key}' hat mehr als {decimals} Nachkommastellen, bitte runde ihn selbst."),
+            Text::JoiningSession { addr } => format!("Trete der Sitzung unter {addr} bei..."),
+            Text::WaitingForSession { addr } => {
+                format!("Warte darauf, dass die Sitzung unter {addr} startet...")
+            }
+            Text::GeneratingKeys => "Erzeuge öffentlichen/privaten Schlüssel...".into(),
+            Text::YourPublicKey { key } => format!("Dein öffentlicher Schlüssel ist: {key}"),
+            Text::SearchingLocalSession => "Suche nach einer Sitzung im lokalen Netzwerk...".into(),
+            Text::SessionStarted => {
+                "Eine neue Sitzung wurde gestartet, andere können mit folgendem Befehl beitreten:"
+                    .into()
+            }
+            Text::SessionCode { code } => format!("Statt der --address können sie auch den Sitzungscode verwenden: --join={code}"),
+            Text::AlsoReachableAt { family } => format!("Über {family} kann auch mit folgendem Befehl beigetreten werden:"),
+            Text::PressEnterToStart => {
+                "\nDrücke ENTER, um den Benchmark zu starten, sobald alle beigetreten sind.".into()
+            }
+            Text::PauseHint => {
+                "Gib /pause ein, um den Start zu verhindern, bis du /resume eingibst.".into()
+            }
+            Text::ParticipantsHeader => "\n-- Teilnehmende --".into(),
+            Text::StaleParticipant { key, name } => {
+                format!("{key} - {name} (möglicherweise getrennt)")
+            }
+            Text::ParticipantDisconnected { name } => format!("\n{name} hat die Verbindung getrennt"),
+            Text::LobbyPausedByYou => {
+                "Die Lobby ist pausiert, gib /resume ein, um den Start wieder zu erlauben.".into()
+            }
+            Text::LobbyResumedByYou => {
+                "Die Lobby ist fortgesetzt, drücke ENTER, um den Benchmark zu starten.".into()
+            }
+            Text::CannotStartWhilePaused => {
+                "Die Lobby ist pausiert, gib zuerst /resume ein.".into()
+            }
+            Text::LobbyPaused => "\nDie Lobby wurde pausiert, der Benchmark startet erst, wenn sie fortgesetzt wird.".into(),
+            Text::LobbyResumed => {
+                "\nDie Lobby wurde fortgesetzt, der Benchmark kann jetzt gestartet werden.".into()
+            }
+            Text::LobbyClosesIn { secs } => format!(
+                "Die Lobby schließt in {}:{:02} Minuten, der Benchmark startet dann, wenn genug Teilnehmende beigetreten sind.",
+                secs / 60,
+                secs % 60
+            ),
+            Text::LobbyTimedOut => {
+                "Die Lobby ist abgelaufen, bevor der Benchmark gestartet werden konnte, die Sitzung wird abgebrochen.".into()
+            }
+            Text::SharesTimedOut { names } => format!("Von {names} wurden vor dem --share-timeout keine Anteile empfangen, der Benchmark wird abgebrochen."),
+            Text::NotEnoughParticipants { needed } => format!(
+                "Noch kein Start möglich, zum Schutz der Privatsphäre sind mindestens {needed} Teilnehmende nötig."
+            ),
+            Text::ResultVerified => {
+                "Das Ergebnis stimmt mit den Summen aller Teilnehmenden überein.".into()
+            }
+            Text::ResultMismatch => "\nWARNUNG: Das Ergebnis der ersten teilnehmenden Person stimmt NICHT mit den Summen der Teilnehmenden überein! Es ist falsch oder wurde manipuliert, der Benchmark wird abgebrochen.".into(),
+            Text::DuplicateName { name, renamed } => format!(
+                "Es gibt bereits eine teilnehmende Person namens {name}, die neue Person wird als {renamed} angezeigt."
+            ),
+            Text::NameTaken { name } => format!(
+                "Dein Name wird bereits von einer anderen teilnehmenden Person verwendet, du wirst als {name} angezeigt. Wähle am besten einen anderen --name."
+            ),
+            Text::MinParticipantsInLobby { needed } => {
+                format!("Um den Benchmark zu starten, sind mindestens {needed} Teilnehmende nötig.")
+            }
+            Text::StartingBenchmark => "Starte den Benchmark mit den aktuellen Teilnehmenden...".into(),
+            Text::NoisySession { epsilon, sensitivity } => format!("Alle Teilnehmenden fügen ihren Werten zufälliges Rauschen hinzu (Epsilon = {epsilon}, Sensitivität = {sensitivity}), die Ergebnisse sind daher nur ungefähr."),
+            Text::ConfirmParticipants => "Bitte überprüfe die Teilnehmenden. Möchtest du am Benchmark teilnehmen? [Y/n]".into(),
+            Text::JoiningBenchmark => {
+                "Ok, nehme mit den aktuellen Teilnehmenden am Benchmark teil...".into()
+            }
+            Text::DryRunComplete => "Probelauf abgeschlossen: die Eingabe ist gültig und alle Teilnehmenden sind verbunden. Es wurden keine Anteile gesendet.".into(),
+            Text::NotADryRun => "Die Sitzung wurde ohne --dry-run gestartet, verlasse sie, bevor Anteile gesendet werden. Dadurch wird der Benchmark für die anderen abgebrochen.".into(),
+            Text::SharesProgress { received, expected } => {
+                format!("Anteile von {received}/{expected} Teilnehmenden erhalten")
+            }
+            Text::SumsProgress { received, expected } => {
+                format!("Summen von {received}/{expected} Teilnehmenden erhalten")
+            }
+            Text::InvalidConfirmation => {
+                "Ungültige Eingabe, bitte mit 'y' bestätigen oder mit 'n' abbrechen".into()
+            }
+            Text::NotEveryoneAgreed => {
+                "Nicht alle haben der Teilnahme zugestimmt, der Benchmark wird nicht ausgeführt.".into()
+            }
+            Text::BenchmarkCancelled => {
+                "Der Benchmark wurde von einem der Teilnehmenden abgebrochen.".into()
+            }
+            Text::ParticipantLeft { name } => {
+                format!("{name} hat die Sitzung verlassen, der Benchmark wird abgebrochen.")
+            }
+            Text::SomeParticipantLeft => {
+                "Jemand hat die Sitzung verlassen, der Benchmark wird abgebrochen.".into()
+            }
+            Text::Reconnecting { secs } => format!("Die Verbindung zu einer teilnehmenden Person wurde unterbrochen, warte bis zu {secs} Sekunden auf die Wiederherstellung..."),
+            Text::Reconnected => "Die Verbindung wurde wiederhergestellt, der Benchmark wird fortgesetzt.".into(),
+            Text::AverageResults => "\nErgebnisse (Durchschnitt):".into(),
+            Text::TotalResults => "\nErgebnisse (Summe):".into(),
+            Text::RatioResults => "\nVerhältnisse:".into(),
+            Text::GeoMeanResults => "\nErgebnisse (geometrisches Mittel):".into(),
+            Text::HistogramResults => "\nVerteilung:".into(),
+            Text::PartialResults {
+                contributors,
+                participants,
+            } => format!("(unvollständig: {contributors} von {participants} Teilnehmenden)"),
+            Text::KickHint => {
+                "Gib /kick <Name oder Schlüssel> ein, um jemanden aus der Sitzung zu entfernen.".into()
+            }
+            Text::AmbiguousParticipant { prefix } => format!("Die Schlüssel mehrerer Teilnehmender beginnen mit '{prefix}', bitte gib mehr vom Schlüssel ein."),
+            Text::ParticipantKicked { name } => format!("{name} wurde aus der Sitzung entfernt."),
+            Text::RemovedFromSession => "Du wurdest aus der Sitzung entfernt.".into(),
+            Text::ParticipantsTitle => " Teilnehmende ".into(),
+            Text::MessagesTitle => " Meldungen ".into(),
+            Text::ResultsTitle => " Ergebnisse ".into(),
+            Text::PressAnyKeyToQuit => "Zum Beenden eine beliebige Taste drücken.".into(),
+            _ => return None,
+        };
+        Some(text)
+    }
+}
@@ -0,0 +1,141 @@
+//! A transport that dials all connections through a SOCKS5 proxy (`--proxy`), for participants in
+//! networks that do not allow direct outgoing connections.
+//!
+//! The transport can only dial, so it cannot be used to start a session. Host names are resolved
+//! by the proxy, since the local network might not be able to resolve them.
+
+use std::{
+    borrow::Cow,
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use libp2p::{
+    core::transport::{ListenerId, TransportError, TransportEvent},
+    multiaddr::Protocol,
+    Multiaddr, Transport,
+};
+use tokio::net::TcpStream;
+use tokio_socks::{tcp::Socks5Stream, TargetAddr};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    /// The `host:port` of the proxy.
+    addr: String,
+    auth: Option<(String, String)>,
+}
+
+/// Parses a proxy URL of the form `socks5://[user:password@]host:port`.
+pub fn parse_proxy(url: &str) -> Result<Proxy, String> {
+    let Some(rest) = url
+        .strip_prefix("socks5://")
+        .or_else(|| url.strip_prefix("socks5h://"))
+    else {
+        return Err(format!("{url} is not a socks5:// URL"));
+    };
+    let rest = rest.trim_end_matches('/');
+    let (auth, addr) = match rest.rsplit_once('@') {
+        Some((auth, addr)) => match auth.split_once(':') {
+            Some((user, password)) => (Some((user.to_string(), password.to_string())), addr),
+            None => {
+                return Err(format!(
+                    "{url} must specify the credentials as user:password"
+                ))
+            }
+        },
+        None => (None, rest),
+    };
+    match addr.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => Ok(Proxy {
+            addr: addr.to_string(),
+            auth,
+        }),
+        _ => Err(format!("{url} must specify the host and port of the proxy")),
+    }
+}
+
+pub struct Socks5Transport {
+    proxy: Proxy,
+}
+
+impl Socks5Transport {
+    pub fn new(proxy: Proxy) -> Self {
+        Socks5Transport { proxy }
+    }
+}
+
+/// The address that the proxy should connect to, if the multiaddr is a TCP address.
+fn target(addr: &Multiaddr) -> Option<TargetAddr<'static>> {
+    let mut protocols = addr.iter();
+    let host = protocols.next()?;
+    let Some(Protocol::Tcp(port)) = protocols.next() else {
+        return None;
+    };
+    match host {
+        Protocol::Ip4(ip) => Some(TargetAddr::Ip(SocketAddr::new(ip.into(), port))),
+        Protocol::Ip6(ip) => Some(TargetAddr::Ip(SocketAddr::new(ip.into(), port))),
+        Protocol::Dns(host) | Protocol::Dns4(host) | Protocol::Dns6(host) => {
+            Some(TargetAddr::Domain(Cow::Owned(host.to_string()), port))
+        }
+        _ => None,
+    }
+}
+
+impl Transport for Socks5Transport {
+    type Output = Compat<Socks5Stream<TcpStream>>;
+    type Error = io::Error;
+    type ListenerUpgrade = futures::future::Pending<Result<Self::Output, Self::Error>>;
+    type Dial = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+
+    fn listen_on(
+        &mut self,
+        _id: ListenerId,
+        addr: Multiaddr,
+    ) -> Result<(), TransportError<Self::Error>> {
+        Err(TransportError::MultiaddrNotSupported(addr))
+    }
+
+    fn remove_listener(&mut self, _id: ListenerId) -> bool {
+        false
+    }
+
+    fn dial(&mut self, addr: Multiaddr) -> Result<Self::Dial, TransportError<Self::Error>> {
+        let Some(target) = target(&addr) else {
+            return Err(TransportError::MultiaddrNotSupported(addr));
+        };
+        let Proxy { addr, auth } = self.proxy.clone();
+        Ok(async move {
+            let stream = match &auth {
+                Some((user, password)) => {
+                    Socks5Stream::connect_with_password(addr.as_str(), target, user, password).await
+                }
+                None => Socks5Stream::connect(addr.as_str(), target).await,
+            };
+            let stream = stream.map_err(|e| io::Error::other(format!("proxy {addr}: {e}")))?;
+            Ok(stream.compat())
+        }
+        .boxed())
+    }
+
+    fn dial_as_listener(
+        &mut self,
+        addr: Multiaddr,
+    ) -> Result<Self::Dial, TransportError<Self::Error>> {
+        self.dial(addr)
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
+        Poll::Pending
+    }
+
+    fn address_translation(&self, _listen: &Multiaddr, _observed: &Multiaddr) -> Option<Multiaddr> {
+        None
+    }
+}
@@ -0,0 +1,60 @@
+//! Short codes for the address of a session (`--join`), which are easier to read aloud or to
+//! type than a multiaddr.
+//!
+//! A code is the binary form of the multiaddr in Crockford's base32, in groups of 4 characters
+//! separated by dashes, e.g. `0K5G-0W87-0R7T-2` for `/ip4/203.0.113.7/tcp/4001`. When a code is
+//! decoded, the case and the dashes are ignored and the easily confused letters `O`, `I` and `L`
+//! are read as `0` and `1`.
+
+use libp2p::Multiaddr;
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const GROUP_LEN: usize = 4;
+
+/// Encodes the address as a session code.
+pub fn encode(addr: &Multiaddr) -> String {
+    let mut chars = vec![];
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in addr.to_vec() {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            chars.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        chars.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    let groups: Vec<String> = chars
+        .chunks(GROUP_LEN)
+        .map(|group| group.iter().collect())
+        .collect();
+    groups.join("-")
+}
+
+/// Decodes a session code back into the address of the session.
+pub fn decode(code: &str) -> Result<Multiaddr, String> {
+    let invalid = || format!("{code} is not a valid session code");
+    let mut bytes = vec![];
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in code.chars().filter(|c| *c != '-') {
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let Some(value) = ALPHABET.iter().position(|a| *a as char == c) else {
+            return Err(invalid());
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Multiaddr::try_from(bytes).map_err(|_| invalid())
+}
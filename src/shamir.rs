@@ -0,0 +1,89 @@
+//! Shamir secret sharing (`--threshold`), so that the sum can only be reconstructed from the sums
+//! of at least `t` participants, instead of the additive shares that are reconstructed from the
+//! sums of whoever is present.
+//!
+//! Every participant splits its secret into the points of a random polynomial of degree `t - 1`
+//! (with the secret as its value at zero) and sends one point to every participant. Since the
+//! polynomials can be added point by point, the sum of the received points is a point on a
+//! polynomial whose value at zero is the sum of all secrets. Any `t` of these sums determine the
+//! polynomial, while fewer than `t` reveal nothing about it.
+//!
+//! All values are elements of the field of integers modulo the prime `2^61 - 1`, with negative
+//! values represented as `p - |x|`, so the sums must stay within `±(p - 1) / 2`.
+
+use rand::Rng;
+
+pub const PRIME: u64 = (1 << 61) - 1;
+
+fn to_field(value: i128) -> u64 {
+    value.rem_euclid(PRIME as i128) as u64
+}
+
+fn from_field(value: u64) -> i128 {
+    if value > PRIME / 2 {
+        value as i128 - PRIME as i128
+    } else {
+        value as i128
+    }
+}
+
+fn mul(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % PRIME as u128) as u64
+}
+
+fn inverse(a: u64) -> u64 {
+    let mut result = 1;
+    let mut base = a;
+    let mut exponent = PRIME - 2;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Adds two shares (or sums of shares).
+pub fn add(a: i128, b: i128) -> i128 {
+    ((to_field(a) + to_field(b)) % PRIME) as i128
+}
+
+/// Splits the secret into one share per point (which must be distinct and non-zero), so that any
+/// `threshold` of them are needed to reconstruct it.
+pub fn split<R: Rng>(rng: &mut R, secret: i128, threshold: usize, points: &[u64]) -> Vec<i128> {
+    let mut coefficients = vec![to_field(secret)];
+    for _ in 1..threshold {
+        coefficients.push(rng.gen_range(0..PRIME));
+    }
+    points
+        .iter()
+        .map(|x| {
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(0, |y, coefficient| (mul(y, *x) + coefficient) % PRIME);
+            y as i128
+        })
+        .collect()
+}
+
+/// Interpolates the value at zero of the polynomial through the points, using Lagrange
+/// interpolation. This is only the shared secret if there are at least `threshold` points.
+pub fn reconstruct(points: &[(u64, i128)]) -> i128 {
+    let mut secret = 0;
+    for (i, (x_i, y_i)) in points.iter().enumerate() {
+        let mut numerator = 1;
+        let mut denominator = 1;
+        for (j, (x_j, _)) in points.iter().enumerate() {
+            if i != j {
+                numerator = mul(numerator, *x_j);
+                denominator = mul(denominator, (x_j + PRIME - x_i) % PRIME);
+            }
+        }
+        let basis = mul(numerator, inverse(denominator));
+        secret = (secret + mul(to_field(*y_i), basis)) % PRIME;
+    }
+    from_field(secret)
+}
@@ -0,0 +1,291 @@
+//! An interactive terminal interface (`--tui`) that shows the participants, the phase of the
+//! benchmark and the results in place instead of printing them line by line.
+//!
+//! The interface only renders what the event loop reports and translates key presses into the
+//! same commands that are otherwise typed on stdin, so both modes behave exactly the same.
+
+use std::{
+    io::{self, Stdout},
+    sync::Mutex,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use futures::StreamExt;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use crate::Status;
+
+struct Screen {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    status: Status,
+    selected: usize,
+    messages: Vec<String>,
+    results: Option<Vec<String>>,
+}
+
+static SCREEN: Mutex<Option<Screen>> = Mutex::new(None);
+
+/// Restores the terminal when dropped, so that it is not left in raw mode if `main` returns early.
+pub struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        stop(None);
+    }
+}
+
+/// Switches the terminal to the interactive interface until [`stop`] is called.
+pub fn start(status: Status) -> io::Result<Guard> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        hook(info);
+    }));
+    let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    *SCREEN.lock().unwrap() = Some(Screen {
+        terminal,
+        status,
+        selected: 0,
+        messages: vec![],
+        results: None,
+    });
+    redraw();
+    Ok(Guard)
+}
+
+/// Leaves the interface and prints the messages and results, so that they are not lost.
+///
+/// If a hint is given, it is shown until a key is pressed, so that the results can be read.
+pub fn stop(hint: Option<String>) {
+    let Some(mut screen) = SCREEN.lock().unwrap().take() else {
+        return;
+    };
+    if let (Some(hint), Some(_)) = (hint, &screen.results) {
+        screen.status.keys = hint;
+        let _ = screen.draw();
+        while !matches!(
+            event::read(),
+            Ok(Event::Key(KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            })) | Err(_)
+        ) {}
+    }
+    restore();
+    for line in screen.messages.iter() {
+        println!("{line}");
+    }
+    for line in screen.results.iter().flatten() {
+        println!("{line}");
+    }
+}
+
+fn restore() {
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+}
+
+/// Adds a line to the messages, or to the results once [`begin_results`] has been called.
+pub fn print(line: String) {
+    with_screen(|screen| {
+        let lines = line.lines().map(String::from);
+        match &mut screen.results {
+            Some(results) => results.extend(lines),
+            None => screen.messages.extend(lines),
+        }
+    });
+}
+
+/// Opens the results panel, all following lines are shown as results (after the results of
+/// other sessions that have already ended).
+pub fn begin_results() {
+    with_screen(|screen| {
+        screen.results.get_or_insert_with(Vec::new);
+    });
+}
+
+pub fn update(status: Status) {
+    with_screen(|screen| {
+        screen.selected = screen
+            .selected
+            .min(status.participants.len().saturating_sub(1));
+        screen.status = status;
+    });
+}
+
+fn redraw() {
+    with_screen(|_| {});
+}
+
+fn with_screen(f: impl FnOnce(&mut Screen)) {
+    if let Some(screen) = SCREEN.lock().unwrap().as_mut() {
+        f(screen);
+        if let Err(e) = screen.draw() {
+            log::error!("Could not draw the terminal interface: {e}");
+        }
+    }
+}
+
+/// Key presses, translated into the commands that are typed on stdin in plain mode.
+pub struct Keys(Option<EventStream>);
+
+impl Keys {
+    pub fn new(enabled: bool) -> Self {
+        Keys(enabled.then(EventStream::new))
+    }
+
+    /// Waits for the next command, or forever if the interface is not enabled.
+    pub async fn next(&mut self) -> Option<String> {
+        let Some(events) = &mut self.0 else {
+            return std::future::pending().await;
+        };
+        while let Some(Ok(event)) = events.next().await {
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event
+            else {
+                redraw();
+                continue;
+            };
+            let command = match code {
+                KeyCode::Char('q') => quit(),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => quit(),
+                KeyCode::Enter => String::new(),
+                KeyCode::Char('y') => "y".into(),
+                KeyCode::Char('n') => "n".into(),
+                KeyCode::Char('p') => "/pause".into(),
+                KeyCode::Char('r') => "/resume".into(),
+                KeyCode::Char('k') | KeyCode::Delete => match selected() {
+                    Some(key) => format!("/kick {key}"),
+                    None => continue,
+                },
+                KeyCode::Up => {
+                    with_screen(|screen| screen.selected = screen.selected.saturating_sub(1));
+                    continue;
+                }
+                KeyCode::Down => {
+                    with_screen(|screen| {
+                        let last = screen.status.participants.len().saturating_sub(1);
+                        screen.selected = (screen.selected + 1).min(last);
+                    });
+                    continue;
+                }
+                _ => continue,
+            };
+            return Some(command);
+        }
+        None
+    }
+}
+
+fn quit() -> ! {
+    stop(None);
+    std::process::exit(130);
+}
+
+fn selected() -> Option<String> {
+    let screen = SCREEN.lock().unwrap();
+    let screen = screen.as_ref()?;
+    let (key, _, _) = screen.status.participants.get(screen.selected)?;
+    Some(key.clone())
+}
+
+impl Screen {
+    fn draw(&mut self) -> io::Result<()> {
+        let Screen {
+            terminal,
+            status,
+            selected,
+            messages,
+            results,
+        } = self;
+        terminal.draw(|frame| render(frame, status, *selected, messages, results.as_deref()))?;
+        Ok(())
+    }
+}
+
+fn render(
+    frame: &mut Frame,
+    status: &Status,
+    selected: usize,
+    messages: &[String],
+    results: Option<&[String]>,
+) {
+    let [participants_title, messages_title, results_title] = &status.titles;
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+    let phase = Paragraph::new(status.phase.as_str()).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(phase, rows[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[1]);
+    let items: Vec<ListItem> = status
+        .participants
+        .iter()
+        .map(|(_, name, stale)| {
+            let style = if *stale {
+                Style::default().add_modifier(Modifier::DIM)
+            } else {
+                Style::default()
+            };
+            ListItem::new(name.as_str()).style(style)
+        })
+        .collect();
+    let participants = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(participants_title.as_str()),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(participants, columns[0], &mut state);
+
+    match results {
+        Some(results) => {
+            let panels = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(columns[1]);
+            render_lines(frame, panels[0], messages_title, messages);
+            render_lines(frame, panels[1], results_title, results);
+        }
+        None => render_lines(frame, columns[1], messages_title, messages),
+    }
+
+    frame.render_widget(Paragraph::new(status.keys.as_str()), rows[2]);
+}
+
+/// Renders the last lines that fit into the area.
+fn render_lines(frame: &mut Frame, area: Rect, title: &str, lines: &[String]) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let text = lines[lines.len().saturating_sub(visible)..].join("\n");
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
@@ -1,6 +1,7 @@
 use std::{
-    io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Write},
-    process::{Command, Stdio},
+    collections::HashSet,
+    io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Lines, Write},
+    process::{ChildStdout, Command, Stdio},
     thread::{self, sleep},
     time::Duration,
 };
@@ -37,6 +38,15 @@ fn invalid_json() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn wrong_nested_json_types() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/wrong_nested_types.json")?
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("'db.host' is not a number"));
+    Ok(())
+}
+
 #[test]
 fn wrong_json_types() -> Result<(), Box<dyn std::error::Error>> {
     new_command("foo", None, "tests/test_files/wrong_types.json")?
@@ -48,6 +58,286 @@ fn wrong_json_types() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn strict_precision() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/too_precise.json")?
+        .arg("--strict-precision")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The value 3.145 of key 'example2' has more than 2 decimal places",
+        ));
+    Ok(())
+}
+
+#[test]
+fn port_in_use() -> Result<(), Box<dyn std::error::Error>> {
+    let listener = std::net::TcpListener::bind("0.0.0.0:0")?;
+    let port = listener.local_addr()?.port().to_string();
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--port", &port])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(format!(
+            "Could not listen for connections on port {port}"
+        )));
+    Ok(())
+}
+
+#[test]
+fn too_large_value() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/too_large.json")?
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The value 100000000000000000 of key 'example2' is too large",
+        ));
+    Ok(())
+}
+
+#[test]
+fn infinite_value() -> Result<(), Box<dyn std::error::Error>> {
+    // serde_json refuses numbers beyond the range of f64 instead of parsing them as infinity:
+    new_command("foo", None, "tests/test_files/infinite.json")?
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a valid JSON file"));
+    Ok(())
+}
+
+#[test]
+fn several_sessions_need_a_session_per_address() -> Result<(), Box<dyn std::error::Error>> {
+    new_command(
+        "foo",
+        Some("/ip4/127.0.0.1/tcp/1"),
+        "tests/test_files/valid_json.json",
+    )?
+    .args(["--address", "/ip4/127.0.0.1/tcp/2"])
+    .args(["--session", "first"])
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains(
+        "needs a different --session for every --address",
+    ));
+    Ok(())
+}
+
+#[test]
+fn json_logs() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "nonexisting_file.json")?
+        .arg("--json-logs")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            r#"{"event":"NoSuchFile","path":"nonexisting_file.json","level":"warn","#,
+        ));
+    Ok(())
+}
+
+#[test]
+fn input_hash_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--input-hash", &"0".repeat(64)])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Input file hash mismatch"));
+    Ok(())
+}
+
+#[test]
+fn compare_to_missing_file() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--compare-to", "nonexisting_result.json"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No such file"));
+    Ok(())
+}
+
+#[test]
+fn unsupported_key_bits() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--key-bits", "1000"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("not a supported key size"));
+    Ok(())
+}
+
+#[test]
+fn input_from_stdin() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "-")?
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("please also use --lobby-timeout"));
+    new_command("foo", Some("/ip4/127.0.0.1/tcp/1"), "-")?
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("please also use --yes"));
+    let mut cmd = new_command("foo", None, "-")?;
+    cmd.args(["--lobby-timeout", "60"]);
+    assert_cmd::Command::from_std(cmd)
+        .write_stdin("{\"example\": }")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a valid JSON file"));
+    Ok(())
+}
+
+#[test]
+fn invalid_session_code() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--join", "0JGY-D9AF-0VS0-U"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a valid session code"));
+    Ok(())
+}
+
+#[test]
+fn external_addr_without_no_upnp() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--external-addr", "/ip4/203.0.113.7/tcp/4001", "--port", "4001"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--no-upnp"));
+    Ok(())
+}
+
+#[test]
+fn unsupported_scale() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--scale", "50"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("not a supported scale"));
+    Ok(())
+}
+
+#[test]
+fn too_few_min_participants() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--min-participants", "1"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "at least 2 participants are needed",
+        ));
+    Ok(())
+}
+
+#[test]
+fn german_messages() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(CRATE_NAME)?
+        .args([
+            "--lang",
+            "de",
+            "--name",
+            "foo",
+            "--input",
+            "nonexisting_file.json",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Datei nicht gefunden"));
+    Ok(())
+}
+
+#[test]
+fn invalid_noise() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--dp-epsilon", "0", "--dp-sensitivity", "1000"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("0 is not a positive number"));
+    Ok(())
+}
+
+#[test]
+fn completions() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(CRATE_NAME)?
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("--best-effort-deadline"));
+    Ok(())
+}
+
+#[test]
+fn invalid_transcript() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(CRATE_NAME)?
+        .args(["verify-transcript", "tests/test_files/valid_json.json"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("The transcript is NOT valid"));
+    Ok(())
+}
+
+#[test]
+fn input_not_conforming_to_schema() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--schema", "tests/test_files/schema.json"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("the key 'example4' is missing"));
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--schema", "tests/test_files/valid_json.json"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a valid schema"));
+    Ok(())
+}
+
+#[test]
+fn value_out_of_schema_bounds() -> Result<(), Box<dyn std::error::Error>> {
+    new_command(
+        "foo",
+        Some("/ip4/127.0.0.1/tcp/1"),
+        "tests/test_files/valid_json.json",
+    )?
+    .args(["--schema", "tests/test_files/schema_bounds.json"])
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains(
+        "The value 18 of 'example3' is larger than 10",
+    ));
+    Ok(())
+}
+
+#[test]
+fn tui_with_prometheus_output() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--tui", "--output-format", "prometheus"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+    Ok(())
+}
+
+#[test]
+fn invalid_tags() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--tags", "region=eu,small"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "small is not of the form key=value",
+        ));
+    Ok(())
+}
+
+#[test]
+fn participant_without_address() -> Result<(), Box<dyn std::error::Error>> {
+    new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--role", "participant"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Participants need to specify the --address",
+        ));
+    Ok(())
+}
+
 #[test]
 fn no_session_at_address() -> Result<(), Box<dyn std::error::Error>> {
     new_command(
@@ -66,11 +356,18 @@ fn invalid_address() -> Result<(), Box<dyn std::error::Error>> {
     new_command("foo", Some("bar"), "tests/test_files/valid_json.json")?
         .assert()
         .failure()
-        .stderr(predicates::str::contains("InvalidMultiaddr"));
+        .stderr(predicates::str::contains(
+            "invalid value 'bar' for '--address",
+        ));
     Ok(())
 }
 
 #[test]
+#[allow(
+    clippy::io_other_error,
+    clippy::writeln_empty_string,
+    clippy::zombie_processes
+)]
 fn quit_and_rejoin_session() -> Result<(), Box<dyn std::error::Error>> {
     let mut new_session = new_command("foo", None, "tests/test_files/valid_json.json")?;
 
@@ -202,6 +499,11 @@ fn quit_and_rejoin_session() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 #[test]
+#[allow(
+    clippy::io_other_error,
+    clippy::writeln_empty_string,
+    clippy::zombie_processes
+)]
 fn session() -> Result<(), Box<dyn std::error::Error>> {
     let mut new_session = new_command("foo", None, "tests/test_files/valid_json.json")?;
 
@@ -278,13 +580,13 @@ fn session() -> Result<(), Box<dyn std::error::Error>> {
             writer.flush().unwrap();
         }
         if l.contains("example1: ") {
-            example1_correct = l.split(" ").last().unwrap() == "10.00";
+            example1_correct = l.ends_with(": 10.00 (n=3)");
         }
         if l.contains("example2: ") {
-            example2_correct = l.split(" ").last().unwrap() == "15.00";
+            example2_correct = l.ends_with(": 15.00 (n=3)");
         }
         if l.contains("example3: ") {
-            example3_correct = l.split(" ").last().unwrap() == "18.00";
+            example3_correct = l.ends_with(": 18.00 (n=3)");
         }
         if example1_correct && example2_correct && example3_correct {
             break;
@@ -305,6 +607,267 @@ fn session() -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
+#[test]
+fn compare_to_unchanged_input() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = std::env::temp_dir().join("sine-benchmark-compare-to");
+    std::fs::create_dir_all(&dir)?;
+    let result = dir.join("result.json");
+    let result = result.to_str().unwrap();
+    run_session(
+        &[
+            "--min-participants",
+            "2",
+            "--no-upnp",
+            "--port",
+            "47121",
+            "--external-addr",
+            "/ip4/127.0.0.1/tcp/47121",
+            "--save-result",
+            result,
+        ],
+        &["--min-participants", "2", "--no-upnp"],
+        &[("bar", "tests/test_files/valid_json.json")],
+        // the result is saved right after the last value has been printed:
+        &["example3: 18.00 (n=2)"],
+    )?;
+
+    // the note is printed before the session is started:
+    let mut leader = new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(["--compare-to", result])
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let lines = BufReader::new(leader.stdout.take().unwrap()).lines();
+    let unchanged = lines
+        .map_while(Result::ok)
+        .take_while(|l| !l.contains("--address=/ip4/"))
+        .any(|l| l.contains("Note: your input is unchanged since the run that saved"));
+    leader.kill()?;
+    leader.wait()?;
+    assert!(unchanged);
+    Ok(())
+}
+
+#[test]
+fn several_sessions() -> Result<(), Box<dyn std::error::Error>> {
+    run_several_sessions(
+        47101,
+        &["1 y", "2 y"],
+        &[
+            "[1] example1: 10.00 (n=2)",
+            "[2] example1: 15.00 (n=2)",
+            "[2] example3: 23.00 (n=2)",
+        ],
+    )
+}
+
+#[test]
+fn decline_one_of_several_sessions() -> Result<(), Box<dyn std::error::Error>> {
+    run_several_sessions(47111, &["1 y", "2 n"], &["[1] example1: 10.00 (n=2)"])
+}
+
+/// Runs two sessions "one" and "two" with the leaders "foo" (using `valid_json.json` and
+/// `other_json.json` as their inputs) and the participant "bar", who joins both at once and
+/// answers the confirmation of each session with the given line, until the participant has printed
+/// all of the `expected` strings and exited successfully. The leaders listen on `port` and the port
+/// after it, without UPnP.
+fn run_several_sessions(
+    port: u16,
+    answers: &[&str],
+    expected: &[&str],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args = ["--min-participants", "2", "--key-bits", "1024", "--no-upnp"];
+    let mut leaders = vec![];
+    let mut addresses = vec![];
+    for (session, input, port) in [
+        ("one", "tests/test_files/valid_json.json", port),
+        ("two", "tests/test_files/other_json.json", port + 1),
+    ] {
+        let mut leader = new_command("foo", None, input)?
+            .args(args)
+            .args(["--session", session, "--port", &port.to_string()])
+            .args(["--external-addr", &format!("/ip4/127.0.0.1/tcp/{port}")])
+            .stdout(Stdio::piped())
+            .stdin(Stdio::piped())
+            .spawn()?;
+        let mut lines = BufReader::new(leader.stdout.take().unwrap()).lines();
+        let mut writer = BufWriter::new(leader.stdin.take().unwrap());
+        let Some(address) = leader_address(&mut lines) else {
+            leader.kill()?;
+            leader.wait()?;
+            return Err(Box::new(Error::other(
+                "The leader did not print its address",
+            )));
+        };
+        addresses.extend(["--address".to_string(), address]);
+        addresses.extend(["--session".to_string(), session.to_string()]);
+        leaders.push(leader);
+        thread::spawn(move || {
+            for l in lines.map_while(Result::ok) {
+                println!("foo ({session}) > {l}");
+                if l.contains("- bar") {
+                    sleep(Duration::from_millis(200));
+                    let _ = writeln!(writer);
+                    let _ = writer.flush();
+                }
+            }
+        });
+    }
+
+    let mut participant = new_command("bar", None, "tests/test_files/valid_json.json")?
+        .args(args)
+        .args(addresses)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let reader = BufReader::new(participant.stdout.take().unwrap());
+    let mut writer = BufWriter::new(participant.stdin.take().unwrap());
+    let mut found = vec![false; expected.len()];
+    for l in reader.lines().map_while(Result::ok) {
+        println!("bar > {l}");
+        if l.contains("Do you want to join the benchmark?") {
+            let answer = answers
+                .iter()
+                .find(|a| l.starts_with(&format!("[{}]", &a[..1])));
+            sleep(Duration::from_millis(200));
+            writeln!(writer, "{}", answer.unwrap())?;
+            writer.flush()?;
+        }
+        for (i, e) in expected.iter().enumerate() {
+            if l.contains(e) {
+                found[i] = true;
+            }
+        }
+    }
+    let status = participant.wait()?;
+
+    for mut leader in leaders {
+        leader.kill()?;
+        leader.wait()?;
+    }
+
+    if status.success() && found.iter().all(|f| *f) {
+        Ok(())
+    } else {
+        Err(Box::new(Error::other("Wrong results")))
+    }
+}
+
+/// Runs a benchmark with the leader "foo" (using `valid_json.json` as its input) and the given
+/// participants and their inputs, until the leader has printed all of the `expected` strings.
+fn run_session(
+    leader_args: &[&str],
+    participant_args: &[&str],
+    participants: &[(&str, &str)],
+    expected: &[&str],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut leader = new_command("foo", None, "tests/test_files/valid_json.json")?
+        .args(leader_args)
+        .stdout(Stdio::piped())
+        .stdin(Stdio::piped())
+        .spawn()?;
+    let stdout = leader.stdout.take().unwrap();
+    let reader = BufReader::new(stdout);
+    let stdin = leader.stdin.take().unwrap();
+    let mut writer = BufWriter::new(stdin);
+    let mut lines = reader.lines();
+
+    let Some(address) = leader_address(&mut lines) else {
+        leader.kill()?;
+        leader.wait()?;
+        return Err(Box::new(Error::other("The leader did not print its address")));
+    };
+
+    let mut threads = vec![];
+    for (name, input) in participants {
+        let (name, input) = (name.to_string(), input.to_string());
+        let address = address.clone();
+        let participant_args: Vec<String> =
+            participant_args.iter().map(|a| a.to_string()).collect();
+        threads.push(thread::spawn(move || {
+            let mut participant = new_command(&name, Some(&address), &input)
+                .unwrap()
+                .args(participant_args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+
+            let stdout = participant.stdout.take().unwrap();
+            let reader = BufReader::new(stdout);
+            let stdin = participant.stdin.take().unwrap();
+            let mut writer = BufWriter::new(stdin);
+
+            for l in reader.lines().map_while(Result::ok) {
+                println!("{name} > {l}");
+
+                if l.contains("Do you want to join the benchmark?") {
+                    sleep(Duration::from_millis(200));
+                    writeln!(writer, "y").unwrap();
+                    writer.flush().unwrap();
+                }
+
+                if l.contains("results") {
+                    break;
+                }
+            }
+            let _ = participant.kill();
+            participant.wait().unwrap();
+        }));
+    }
+
+    let mut joined = HashSet::new();
+    let mut started = false;
+    let mut found = vec![false; expected.len()];
+    for l in lines.map_while(Result::ok) {
+        println!("foo > {}", l);
+        for (name, _) in participants {
+            if l.contains(&format!("- {name}")) {
+                joined.insert(name);
+            }
+        }
+        if !started && joined.len() == participants.len() {
+            sleep(Duration::from_millis(200));
+            writeln!(writer)?;
+            writer.flush()?;
+            started = true;
+        }
+        for (i, e) in expected.iter().enumerate() {
+            if l.contains(e) {
+                found[i] = true;
+            }
+        }
+        if found.iter().all(|f| *f) {
+            break;
+        }
+    }
+
+    sleep(Duration::from_millis(200));
+    leader.kill()?;
+    leader.wait()?;
+
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    if found.iter().all(|f| *f) {
+        Ok(())
+    } else {
+        Err(Box::new(Error::other("Wrong results")))
+    }
+}
+
+/// Reads the output of the leader until it prints the address that participants can join, or
+/// `None` if the leader exits before that.
+fn leader_address(lines: &mut Lines<BufReader<ChildStdout>>) -> Option<String> {
+    lines.map_while(Result::ok).find_map(|l| {
+        println!("foo > {l}");
+        l.split(' ')
+            .find(|s| s.contains("--address=/ip4/"))
+            .map(|s| s.replace("--address=", ""))
+    })
+}
+
+#[allow(clippy::single_match)]
 fn new_command(
     name: &str,
     address: Option<&str>,
@@ -312,6 +875,7 @@ fn new_command(
 ) -> Result<Command, Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin(CRATE_NAME)?;
 
+    cmd.args(["--lang", "en"]);
     cmd.args(["--name", name]);
 
     match address {